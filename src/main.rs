@@ -1,20 +1,26 @@
-use chrono::{Duration, NaiveDate};
-use serde::Deserialize;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::cmp::{max, min};
 use std::env;
 use std::error::Error;
 use std::fs;
 use std::io::{self, Write};
+use std::sync::LazyLock;
 
 /// Represents a single absence period from the JSON input.
+///
+/// Dates are kept as raw strings here so that `parse_and_validate_absences`
+/// can run them through the same flexible parser used interactively, rather
+/// than requiring strict ISO-8601 at the serde layer.
 #[derive(Deserialize)]
 struct AbsencePeriod {
-    start_date: NaiveDate,
-    end_date: NaiveDate,
+    start_date: String,
+    end_date: String,
 }
 
 /// Holds the results of a calculation for a single absence period.
-#[derive(Debug, PartialEq)] // Added for testing purposes
+#[derive(Debug, PartialEq, Serialize)] // Debug/PartialEq for tests; Serialize for JSON output
 struct CalculationResult {
     absence_start: NaiveDate,
     absence_end: NaiveDate,
@@ -23,11 +29,149 @@ struct CalculationResult {
     total_days_in_window: i64,
 }
 
+/// Maps a (case-insensitive) month name or abbreviation to its number.
+fn month_from_name(name: &str) -> Option<u32> {
+    let month = match name {
+        "january" | "jan" => 1,
+        "february" | "feb" => 2,
+        "march" | "mar" => 3,
+        "april" | "apr" => 4,
+        "may" => 5,
+        "june" | "jun" => 6,
+        "july" | "jul" => 7,
+        "august" | "aug" => 8,
+        "september" | "sep" | "sept" => 9,
+        "october" | "oct" => 10,
+        "november" | "nov" => 11,
+        "december" | "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+/// Parses a day token that may carry an ordinal suffix, e.g. "15th" or "3rd".
+fn parse_ordinal_day(token: &str) -> Option<u32> {
+    let digits: String = token.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse::<u32>().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// Resolves human expressions like "today", "3 weeks ago", "last january", or
+/// "15th march 2023" into a concrete date.
+///
+/// `reference` anchors relative expressions ("yesterday", "N days ago"); in
+/// practice this is today's date, but it is threaded through explicitly so
+/// the resolver stays deterministic and testable.
+fn resolve_natural_date(input: &str, reference: NaiveDate) -> Option<NaiveDate> {
+    let text = input.trim().to_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["today"] => Some(reference),
+        ["yesterday"] => Some(reference - Duration::days(1)),
+        [n, unit, "ago"] => {
+            let amount: u64 = n.parse().ok()?;
+            match unit.trim_end_matches('s') {
+                "day" => Some(reference - Duration::days(amount as i64)),
+                "week" => Some(reference - Duration::days(amount as i64 * 7)),
+                "month" => reference.checked_sub_months(Months::new(amount as u32)),
+                "year" => reference.checked_sub_months(Months::new(amount as u32 * 12)),
+                _ => None,
+            }
+        }
+        ["last", month_name] => {
+            let month = month_from_name(month_name)?;
+            let year = if month >= reference.month() {
+                reference.year() - 1
+            } else {
+                reference.year()
+            };
+            NaiveDate::from_ymd_opt(year, month, 1)
+        }
+        [day_token, month_name] => {
+            let day = parse_ordinal_day(day_token)?;
+            let month = month_from_name(month_name)?;
+            let candidate = NaiveDate::from_ymd_opt(reference.year(), month, day)?;
+            if candidate > reference {
+                NaiveDate::from_ymd_opt(reference.year() - 1, month, day)
+            } else {
+                Some(candidate)
+            }
+        }
+        [day_token, month_name, year_token] => {
+            let day = parse_ordinal_day(day_token)?;
+            let month = month_from_name(month_name)?;
+            let year: i32 = year_token.parse().ok()?;
+            NaiveDate::from_ymd_opt(year, month, day)
+        }
+        _ => None,
+    }
+}
+
+/// Candidate `(regex, strftime format)` pairs tried in order by
+/// `parse_with_known_formats`. Day-first formats are listed ahead of
+/// anything that could be read month-first, since this is a UK tool.
+const FORMATS: &[(&str, &str)] = &[
+    (r"^\d{4}-\d{3}$", "%Y-%j"), // ISO-8601 ordinal date, e.g. 2023-074
+    (r"^\d{4}-\d{2}-\d{2}$", "%Y-%m-%d"),
+    (r"^\d{2}/\d{2}/\d{4}$", "%d/%m/%Y"),
+    (r"^\d{2}-\d{2}-\d{4}$", "%d-%m-%Y"),
+    (r"^\d{1,2} [A-Za-z]{3} \d{4}$", "%d %b %Y"),
+    (r"^\d{1,2} [A-Za-z]+ \d{4}$", "%d %B %Y"),
+];
+
+/// `FORMATS` with each pattern compiled once, rather than on every call to
+/// `parse_with_known_formats` — this runs on every interactive input line
+/// and every JSON period, so recompiling per-call would be wasteful.
+static COMPILED_FORMATS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    FORMATS
+        .iter()
+        .map(|(pattern, fmt)| {
+            (
+                Regex::new(pattern).expect("FORMATS entries are valid regexes"),
+                *fmt,
+            )
+        })
+        .collect()
+});
+
+/// Tries each entry in `FORMATS` in order, parsing with the first regex that
+/// matches the trimmed input. Inputs like `03/04/2023`, where both the day
+/// and month fall within 1-12, are genuinely ambiguous; since this is a UK
+/// tool they are read day-first and a warning is printed so the user can
+/// correct it if that's not what was meant.
+fn parse_with_known_formats(input: &str) -> Option<NaiveDate> {
+    let trimmed = input.trim();
+    for (re, fmt) in COMPILED_FORMATS.iter() {
+        if !re.is_match(trimmed) {
+            continue;
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, fmt) {
+            if matches!(*fmt, "%d/%m/%Y" | "%d-%m-%Y") && date.day() <= 12 && date.month() <= 12 {
+                eprintln!(
+                    "Warning: '{}' is ambiguous; interpreting as day-first ({}).",
+                    trimmed, date
+                );
+            }
+            return Some(date);
+        }
+    }
+    None
+}
+
+/// Parses a date accepting any format in `FORMATS` as well as the
+/// natural-language and relative expressions handled by `resolve_natural_date`.
+fn parse_flexible_date(input: &str, reference: NaiveDate) -> Option<NaiveDate> {
+    parse_with_known_formats(input).or_else(|| resolve_natural_date(input, reference))
+}
+
 /// Prompts the user for a date and parses it.
 ///
 /// This function will repeatedly ask the user for input until a valid date
-/// in the "YYYY-MM-DD" format is entered. If the user enters an empty line,
-/// it returns `None`, which is used as a signal to stop input.
+/// is entered, either in one of the `FORMATS` (e.g. "YYYY-MM-DD", "DD/MM/YYYY")
+/// or as a natural-language or relative expression (e.g. "yesterday",
+/// "3 weeks ago", "15th march 2023").
+/// If the user enters an empty line, it returns `None`, which is used as a
+/// signal to stop input.
 ///
 /// # Arguments
 ///
@@ -53,10 +197,12 @@ fn get_date_from_user(prompt: &str) -> Option<NaiveDate> {
             return None;
         }
 
-        match NaiveDate::parse_from_str(trimmed_input, "%Y-%m-%d") {
-            Ok(date) => return Some(date),
-            Err(_) => {
-                println!("Invalid date format. Please use YYYY-MM-DD and try again.");
+        match parse_flexible_date(trimmed_input, Local::now().date_naive()) {
+            Some(date) => return Some(date),
+            None => {
+                println!(
+                    "Invalid date. Try YYYY-MM-DD, DD/MM/YYYY, \"15 Jan 2023\", or an expression like \"3 weeks ago\" or \"last january\"."
+                );
             }
         }
     }
@@ -98,21 +244,29 @@ fn get_absences_from_interactive() -> Vec<(NaiveDate, NaiveDate)> {
 /// Reads absence periods from a JSON file.
 /// The JSON file should be an array of objects, each with "start_date" and "end_date".
 /// e.g., `[{"start_date": "YYYY-MM-DD", "end_date": "YYYY-MM-DD"}]`
+/// Dates may also use the natural-language and relative expressions accepted
+/// by `get_date_from_user` (e.g. "15th march 2023", "3 weeks ago").
 fn parse_and_validate_absences(data: &str) -> Result<Vec<(NaiveDate, NaiveDate)>, Box<dyn Error>> {
     let parsed_periods: Vec<AbsencePeriod> = serde_json::from_str(data)?;
+    let reference = Local::now().date_naive();
 
     // Validate dates and convert to the tuple format used by the rest of the program.
     let mut absence_periods = Vec::new();
     for period in parsed_periods {
-        if period.end_date < period.start_date {
+        let start_date = parse_flexible_date(&period.start_date, reference)
+            .ok_or_else(|| format!("Could not parse start date '{}'", period.start_date))?;
+        let end_date = parse_flexible_date(&period.end_date, reference)
+            .ok_or_else(|| format!("Could not parse end date '{}'", period.end_date))?;
+
+        if end_date < start_date {
             // Using eprintln! to write to standard error for error messages.
             eprintln!(
                 "Warning: Invalid period in JSON file. End date {} is before start date {}. Skipping.",
-                period.end_date, period.start_date
+                end_date, start_date
             );
             continue;
         }
-        absence_periods.push((period.start_date, period.end_date));
+        absence_periods.push((start_date, end_date));
     }
     Ok(absence_periods)
 }
@@ -123,19 +277,17 @@ fn get_absences_from_file(path: &str) -> Result<Vec<(NaiveDate, NaiveDate)>, Box
     parse_and_validate_absences(&data)
 }
 
-/// Performs the absence calculation for all periods.
-///
-/// For each absence period, it defines a 365-day rolling window ending on the
-/// absence's end date. It then sums the days of all absences that fall
-/// within that specific window.
-fn calculate_rolling_absences(
-    absence_periods: &[(NaiveDate, NaiveDate)],
-) -> Vec<CalculationResult> {
-    if absence_periods.is_empty() {
-        return Vec::new();
-    }
+/// The default ILR rolling-window length in days.
+const DEFAULT_WINDOW_DAYS: i64 = 365;
 
-    // --- Merge overlapping and adjacent intervals to prevent double-counting ---
+/// The default ILR absence limit within a rolling window.
+const DEFAULT_BREACH_THRESHOLD_DAYS: i64 = 180;
+
+/// Merges overlapping and adjacent intervals so later day-counting never
+/// double-counts a day covered by more than one absence period.
+fn merge_absence_periods(
+    absence_periods: &[(NaiveDate, NaiveDate)],
+) -> Vec<(NaiveDate, NaiveDate)> {
     let mut sorted_periods = absence_periods.to_vec();
     sorted_periods.sort_by_key(|(start, _)| *start);
 
@@ -152,26 +304,58 @@ fn calculate_rolling_absences(
             merged_periods.push((start, end));
         }
     }
+    merged_periods
+}
+
+/// Sums the clipped overlap of each merged interval with `[window_start, window_end]`.
+fn sum_overlap_days(
+    merged_periods: &[(NaiveDate, NaiveDate)],
+    window_start: NaiveDate,
+    window_end: NaiveDate,
+) -> i64 {
+    merged_periods
+        .iter()
+        .filter_map(|(period_start, period_end)| {
+            let overlap_start = max(*period_start, window_start);
+            let overlap_end = min(*period_end, window_end);
+
+            if overlap_start <= overlap_end {
+                Some((overlap_end - overlap_start).num_days() + 1)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+/// Performs the absence calculation for all periods.
+///
+/// For each absence period, it defines a `window_days`-long rolling window
+/// ending on the absence's end date. It then sums the days of all absences
+/// that fall within that specific window.
+///
+/// This only covers `WindowMode::Rolling`, rather than taking a `WindowMode`
+/// directly: its return type (one `CalculationResult` per absence) doesn't
+/// fit `CalendarYears`, which reports per-block totals via
+/// `calculate_calendar_year_blocks` instead. `main` dispatches on
+/// `WindowMode` and calls whichever of the two matches.
+fn calculate_rolling_absences(
+    absence_periods: &[(NaiveDate, NaiveDate)],
+    window_days: i64,
+) -> Vec<CalculationResult> {
+    if absence_periods.is_empty() {
+        return Vec::new();
+    }
+
+    let merged_periods = merge_absence_periods(absence_periods);
 
     let mut results = Vec::new();
     for (absence_start, absence_end) in absence_periods.iter() {
         let calculation_end = *absence_end;
-        let calculation_start = calculation_end - Duration::days(365);
-
-        // Calculate the total using the MERGED periods.
-        let total_absence_days: i64 = merged_periods
-            .iter()
-            .filter_map(|(period_start, period_end)| {
-                let overlap_start = max(*period_start, calculation_start);
-                let overlap_end = min(*period_end, calculation_end);
+        let calculation_start = calculation_end - Duration::days(window_days);
 
-                if overlap_start <= overlap_end {
-                    Some((overlap_end - overlap_start).num_days() + 1)
-                } else {
-                    None
-                }
-            })
-            .sum();
+        let total_absence_days =
+            sum_overlap_days(&merged_periods, calculation_start, calculation_end);
 
         results.push(CalculationResult {
             absence_start: *absence_start,
@@ -184,10 +368,141 @@ fn calculate_rolling_absences(
     results
 }
 
-/// Calculates and prints the results for the given absence periods.
-fn calculate_and_print_results(absence_periods: &[(NaiveDate, NaiveDate)]) {
-    println!("\n--- Absence Calculation Results (365-day rolling window) ---");
-    let results = calculate_rolling_absences(absence_periods);
+/// Per-block totals for `WindowMode::CalendarYears`, reporting how many
+/// absence days fall within each discrete 12-month block starting at the
+/// configured anchor date.
+#[derive(Debug, PartialEq, Serialize)]
+struct CalendarBlockResult {
+    block_start: NaiveDate,
+    block_end: NaiveDate,
+    total_days_in_block: i64,
+}
+
+/// Selects how absence windows are reckoned: a sliding window of a fixed
+/// length (`calculate_rolling_absences`), or discrete 12-month blocks
+/// counted from an anchor date (`calculate_calendar_year_blocks`). The two
+/// modes produce differently-shaped results, so `main` matches on this
+/// rather than threading it into a single calculation function.
+#[derive(Debug, Clone, Copy)]
+enum WindowMode {
+    Rolling { days: i64 },
+    CalendarYears { anchor: NaiveDate },
+}
+
+/// Splits the span covered by `absence_periods` into consecutive 12-month
+/// blocks starting at `anchor`, advanced with `Months::new(12)` so leap
+/// years and month-end anchors (e.g. 29 Feb, 31 Jan) are handled correctly,
+/// then sums each absence's overlap with every block it touches. Blocks run
+/// from `anchor` up to the last absence end; absence days before `anchor`
+/// fall in no block and are not counted.
+fn calculate_calendar_year_blocks(
+    absence_periods: &[(NaiveDate, NaiveDate)],
+    anchor: NaiveDate,
+) -> Vec<CalendarBlockResult> {
+    if absence_periods.is_empty() {
+        return Vec::new();
+    }
+
+    let merged_periods = merge_absence_periods(absence_periods);
+    let last_end = merged_periods.iter().map(|&(_, end)| end).max().unwrap();
+
+    let mut blocks = Vec::new();
+    let mut block_start = anchor;
+    while block_start <= last_end {
+        let block_end = block_start
+            .checked_add_months(Months::new(12))
+            .expect("block_start + 12 months overflowed NaiveDate")
+            - Duration::days(1);
+        let total_days_in_block = sum_overlap_days(&merged_periods, block_start, block_end);
+
+        blocks.push(CalendarBlockResult {
+            block_start,
+            block_end,
+            total_days_in_block,
+        });
+        block_start = block_end + Duration::days(1);
+    }
+    blocks
+}
+
+/// Writes `blocks` as pretty-printed JSON to `writer`.
+fn write_calendar_blocks_json<W: Write>(
+    blocks: &[CalendarBlockResult],
+    mut writer: W,
+) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer_pretty(&mut writer, blocks)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Writes one CSV row per calendar block, with a header row, to `writer`.
+fn write_calendar_blocks_csv<W: Write>(blocks: &[CalendarBlockResult], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "block_start,block_end,total_days_in_block")?;
+    for block in blocks {
+        writeln!(
+            writer,
+            "{},{},{}",
+            block.block_start.format("%Y-%m-%d"),
+            block.block_end.format("%Y-%m-%d"),
+            block.total_days_in_block
+        )?;
+    }
+    Ok(())
+}
+
+/// The rolling window with the highest absence-day total across all
+/// candidate windows, together with whether it breaches `limit`.
+#[derive(Debug, PartialEq, Serialize)]
+struct WorstWindow {
+    window_start: NaiveDate,
+    window_end: NaiveDate,
+    total_days: i64,
+    breaches_limit: bool,
+}
+
+/// Finds the `window_days`-long rolling window with the highest total
+/// absence days across all given periods, and reports whether it breaches
+/// `limit`.
+///
+/// The maximum is always attained by a window whose end coincides with some
+/// merged interval's end date: shifting a fixed-width window rightward until
+/// it hits an absence end can only add covered days, and shifting it
+/// leftward from there only loses them. So it suffices to test one window
+/// per merged-interval end date, rather than every possible day.
+fn find_worst_window(
+    absence_periods: &[(NaiveDate, NaiveDate)],
+    window_days: i64,
+    limit: i64,
+) -> Option<WorstWindow> {
+    if absence_periods.is_empty() {
+        return None;
+    }
+
+    let merged_periods = merge_absence_periods(absence_periods);
+
+    merged_periods
+        .iter()
+        .map(|&(_, end)| {
+            let window_start = end - Duration::days(window_days);
+            let total_days = sum_overlap_days(&merged_periods, window_start, end);
+            WorstWindow {
+                window_start,
+                window_end: end,
+                total_days,
+                breaches_limit: total_days > limit,
+            }
+        })
+        .max_by_key(|w| w.total_days)
+}
+
+/// Calculates and prints the rolling-window results for the given absence
+/// periods, using a `window_days`-long sliding window.
+fn calculate_and_print_results(absence_periods: &[(NaiveDate, NaiveDate)], window_days: i64) {
+    println!(
+        "\n--- Absence Calculation Results ({}-day rolling window) ---",
+        window_days
+    );
+    let results = calculate_rolling_absences(absence_periods, window_days);
 
     for result in results {
         println!(
@@ -195,23 +510,186 @@ fn calculate_and_print_results(absence_periods: &[(NaiveDate, NaiveDate)]) {
             result.absence_start, result.absence_end
         );
         println!(
-            "  365-day calculation window: {} to {}",
-            result.window_start, result.window_end
+            "  {}-day calculation window: {} to {}",
+            window_days, result.window_start, result.window_end
         );
         println!(
             "  Total absence days within this window: {}",
             result.total_days_in_window
         );
     }
+
+    if let Some(worst) =
+        find_worst_window(absence_periods, window_days, DEFAULT_BREACH_THRESHOLD_DAYS)
+    {
+        println!("\n--- Worst-Case Window ---");
+        println!(
+            "  Window: {} to {} ({} days)",
+            worst.window_start, worst.window_end, worst.total_days
+        );
+        if worst.breaches_limit {
+            println!(
+                "  Result: FAIL - exceeds the {}-day limit.",
+                DEFAULT_BREACH_THRESHOLD_DAYS
+            );
+        } else {
+            println!(
+                "  Result: PASS - within the {}-day limit.",
+                DEFAULT_BREACH_THRESHOLD_DAYS
+            );
+        }
+    }
+}
+
+/// Calculates and prints calendar-year block results anchored at `anchor`.
+fn calculate_and_print_calendar_blocks(absence_periods: &[(NaiveDate, NaiveDate)], anchor: NaiveDate) {
+    println!(
+        "\n--- Absence Calculation Results (12-month blocks from {}) ---",
+        anchor
+    );
+    let blocks = calculate_calendar_year_blocks(absence_periods, anchor);
+
+    for block in blocks {
+        println!("\nBlock: {} to {}", block.block_start, block.block_end);
+        println!(
+            "  Total absence days within this block: {}",
+            block.total_days_in_block
+        );
+    }
+}
+
+/// Output format selected via the `--format` flag. `Text` is the default and
+/// prints the prose report: per-absence windows followed by the worst-case
+/// window verdict.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Top-level summary combining every per-absence result with the worst-case
+/// rolling-window verdict. This is what `--format json` and `--format csv`
+/// serialize; the `text` format continues to use `calculate_and_print_results`.
+#[derive(Debug, Serialize)]
+struct AbsenceSummary {
+    results: Vec<CalculationResult>,
+    worst_window: Option<WorstWindow>,
+    breach_threshold_days: i64,
+}
+
+/// Builds the structured summary for `--format json`/`--format csv` from the
+/// same calculations the text output uses.
+fn build_summary(absence_periods: &[(NaiveDate, NaiveDate)], window_days: i64) -> AbsenceSummary {
+    AbsenceSummary {
+        results: calculate_rolling_absences(absence_periods, window_days),
+        worst_window: find_worst_window(absence_periods, window_days, DEFAULT_BREACH_THRESHOLD_DAYS),
+        breach_threshold_days: DEFAULT_BREACH_THRESHOLD_DAYS,
+    }
+}
+
+/// Writes `summary` as pretty-printed JSON to `writer`. Kept generic over
+/// `Write` (rather than hard-coding stdout) so tests can assert the bytes
+/// produced are valid, standalone JSON with nothing else mixed in.
+fn write_json_summary<W: Write>(summary: &AbsenceSummary, mut writer: W) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer_pretty(&mut writer, summary)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Writes one CSV row per absence result, with a header row, to `writer`.
+fn write_csv_summary<W: Write>(summary: &AbsenceSummary, mut writer: W) -> io::Result<()> {
+    writeln!(
+        writer,
+        "absence_start,absence_end,window_start,window_end,total_days_in_window"
+    )?;
+    for result in &summary.results {
+        writeln!(
+            writer,
+            "{},{},{},{},{}",
+            result.absence_start.format("%Y-%m-%d"),
+            result.absence_end.format("%Y-%m-%d"),
+            result.window_start.format("%Y-%m-%d"),
+            result.window_end.format("%Y-%m-%d"),
+            result.total_days_in_window
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes `summary` as pretty-printed JSON to stdout.
+fn print_json_summary(summary: &AbsenceSummary) -> Result<(), Box<dyn Error>> {
+    write_json_summary(summary, io::stdout())
+}
+
+/// Writes one CSV row per absence result, with a header row, to stdout.
+fn print_csv_summary(summary: &AbsenceSummary) {
+    write_csv_summary(summary, io::stdout()).expect("writing to stdout should not fail");
+}
+
+/// Parses CLI arguments into an optional input file path, an output format,
+/// and a window mode. Recognised flags are `--format <text|json|csv>`,
+/// `--window-days <N>` (rolling window length, default 365), and
+/// `--calendar-anchor <date>` (switches to `WindowMode::CalendarYears`,
+/// accepting any format `parse_with_known_formats` understands). Anything
+/// else is treated as the input file path; an unrecognised format value
+/// falls back to `text`.
+fn parse_args(args: &[String]) -> (Option<&str>, OutputFormat, WindowMode) {
+    let mut file_path = None;
+    let mut format = OutputFormat::Text;
+    let mut window_days = DEFAULT_WINDOW_DAYS;
+    let mut calendar_anchor = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                if let Some(value) = args.get(i + 1) {
+                    format = match value.as_str() {
+                        "json" => OutputFormat::Json,
+                        "csv" => OutputFormat::Csv,
+                        _ => OutputFormat::Text,
+                    };
+                    i += 1;
+                }
+            }
+            "--window-days" => {
+                if let Some(value) = args.get(i + 1) {
+                    if let Ok(days) = value.parse::<i64>() {
+                        window_days = days;
+                    }
+                    i += 1;
+                }
+            }
+            "--calendar-anchor" => {
+                if let Some(value) = args.get(i + 1) {
+                    calendar_anchor = parse_with_known_formats(value);
+                    i += 1;
+                }
+            }
+            other => file_path = Some(other),
+        }
+        i += 1;
+    }
+
+    let window_mode = match calendar_anchor {
+        Some(anchor) => WindowMode::CalendarYears { anchor },
+        None => WindowMode::Rolling { days: window_days },
+    };
+
+    (file_path, format, window_mode)
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    let (file_path, format, window_mode) = parse_args(&args);
 
-    let absence_periods = if args.len() > 1 {
+    // These are human-facing progress/usage banners, not calculation output, so
+    // they always go to stderr - otherwise `--format json`/`--format csv` would
+    // have a non-data line ahead of the payload and no longer be pipeable.
+    let absence_periods = if let Some(file_path) = file_path {
         // File input mode
-        let file_path = &args[1];
-        println!("--- Reading absences from {} ---", file_path);
+        eprintln!("--- Reading absences from {} ---", file_path);
         match get_absences_from_file(file_path) {
             Ok(periods) => periods,
             Err(e) => {
@@ -224,18 +702,45 @@ fn main() {
         }
     } else {
         // Interactive mode
-        println!("--- Absence Calculator (Interactive Mode) ---");
-        println!("Usage: Pass a JSON file path as an argument, or enter dates interactively.");
-        println!("Please enter all dates in YYYY-MM-DD format.");
+        eprintln!("--- Absence Calculator (Interactive Mode) ---");
+        eprintln!("Usage: Pass a JSON file path as an argument, or enter dates interactively.");
+        eprintln!("Dates can be entered as YYYY-MM-DD, DD/MM/YYYY, \"15 Jan 2023\", or relative expressions like \"3 weeks ago\".");
+        eprintln!("Add --format json or --format csv for machine-readable output (default: text).");
+        eprintln!(
+            "Add --window-days <N> for a different rolling window, or --calendar-anchor <date> for discrete 12-month blocks."
+        );
         get_absences_from_interactive()
     };
 
     if absence_periods.is_empty() {
-        println!("\nNo absence periods to process. Exiting.");
+        eprintln!("\nNo absence periods to process. Exiting.");
         return;
     }
 
-    calculate_and_print_results(&absence_periods);
+    match window_mode {
+        WindowMode::Rolling { days } => match format {
+            OutputFormat::Text => calculate_and_print_results(&absence_periods, days),
+            OutputFormat::Json => {
+                if let Err(e) = print_json_summary(&build_summary(&absence_periods, days)) {
+                    eprintln!("Error: Failed to write JSON output. Reason: {}", e);
+                }
+            }
+            OutputFormat::Csv => print_csv_summary(&build_summary(&absence_periods, days)),
+        },
+        WindowMode::CalendarYears { anchor } => {
+            let blocks = calculate_calendar_year_blocks(&absence_periods, anchor);
+            match format {
+                OutputFormat::Text => calculate_and_print_calendar_blocks(&absence_periods, anchor),
+                OutputFormat::Json => {
+                    if let Err(e) = write_calendar_blocks_json(&blocks, io::stdout()) {
+                        eprintln!("Error: Failed to write JSON output. Reason: {}", e);
+                    }
+                }
+                OutputFormat::Csv => write_calendar_blocks_csv(&blocks, io::stdout())
+                    .expect("writing to stdout should not fail"),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -282,10 +787,107 @@ mod tests {
         assert!(parse_and_validate_absences(json_data).is_err());
     }
 
+    #[test]
+    fn test_parse_natural_language_json_dates() {
+        let json_data = r#"[{"start_date": "1st january 2023", "end_date": "10th january 2023"}]"#;
+        let expected = vec![(d(2023, 1, 1), d(2023, 1, 10))];
+        assert_eq!(parse_and_validate_absences(json_data).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_with_known_formats() {
+        assert_eq!(
+            parse_with_known_formats("2023-03-15"),
+            Some(d(2023, 3, 15))
+        );
+        assert_eq!(
+            parse_with_known_formats("15/03/2023"),
+            Some(d(2023, 3, 15))
+        );
+        assert_eq!(
+            parse_with_known_formats("15-03-2023"),
+            Some(d(2023, 3, 15))
+        );
+        assert_eq!(parse_with_known_formats("15 Mar 2023"), Some(d(2023, 3, 15)));
+        assert_eq!(
+            parse_with_known_formats("15 March 2023"),
+            Some(d(2023, 3, 15))
+        );
+        assert_eq!(parse_with_known_formats("2023-074"), Some(d(2023, 3, 15)));
+    }
+
+    #[test]
+    fn test_parse_with_known_formats_prefers_day_first() {
+        // 03/04/2023 is ambiguous; a UK tool should read it as 3 April, not 4 March.
+        assert_eq!(
+            parse_with_known_formats("03/04/2023"),
+            Some(d(2023, 4, 3))
+        );
+    }
+
+    #[test]
+    fn test_resolve_natural_date_today_and_yesterday() {
+        let reference = d(2023, 6, 15);
+        assert_eq!(resolve_natural_date("today", reference), Some(reference));
+        assert_eq!(
+            resolve_natural_date("yesterday", reference),
+            Some(d(2023, 6, 14))
+        );
+    }
+
+    #[test]
+    fn test_resolve_natural_date_relative_ago() {
+        let reference = d(2023, 6, 15);
+        assert_eq!(
+            resolve_natural_date("3 weeks ago", reference),
+            Some(d(2023, 5, 25))
+        );
+        assert_eq!(
+            resolve_natural_date("2 months ago", reference),
+            Some(d(2023, 4, 15))
+        );
+        assert_eq!(
+            resolve_natural_date("1 year ago", reference),
+            Some(d(2022, 6, 15))
+        );
+    }
+
+    #[test]
+    fn test_resolve_natural_date_last_month() {
+        // "last january" from a reference in June 2023 should resolve to this year.
+        assert_eq!(
+            resolve_natural_date("last january", d(2023, 6, 15)),
+            Some(d(2023, 1, 1))
+        );
+        // "last december" from a reference in June 2023 should roll back a year.
+        assert_eq!(
+            resolve_natural_date("last december", d(2023, 6, 15)),
+            Some(d(2022, 12, 1))
+        );
+    }
+
+    #[test]
+    fn test_resolve_natural_date_ordinal_with_and_without_year() {
+        assert_eq!(
+            resolve_natural_date("15th march 2023", d(2023, 6, 15)),
+            Some(d(2023, 3, 15))
+        );
+        // Without a year, a past-in-year date resolves to the current year...
+        assert_eq!(
+            resolve_natural_date("1st january", d(2023, 6, 15)),
+            Some(d(2023, 1, 1))
+        );
+        // ...but a date that would be in the future rolls back to last year.
+        assert_eq!(
+            resolve_natural_date("25th december", d(2023, 6, 15)),
+            Some(d(2022, 12, 25))
+        );
+    }
+
     #[test]
     fn test_calculate_single_absence() {
         let periods = vec![(d(2023, 4, 1), d(2023, 4, 10))]; // 10 days
-        let results = calculate_rolling_absences(&periods);
+        let results = calculate_rolling_absences(&periods, DEFAULT_WINDOW_DAYS);
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].total_days_in_window, 10);
         assert_eq!(results[0].window_start, d(2022, 4, 10));
@@ -298,7 +900,7 @@ mod tests {
             (d(2023, 1, 1), d(2023, 1, 10)), // 10 days
             (d(2023, 8, 1), d(2023, 8, 20)), // 20 days
         ];
-        let results = calculate_rolling_absences(&periods);
+        let results = calculate_rolling_absences(&periods, DEFAULT_WINDOW_DAYS);
         assert_eq!(results.len(), 2);
 
         // For the first period, its window only contains itself.
@@ -316,7 +918,7 @@ mod tests {
             (d(2023, 3, 1), d(2023, 3, 15)),  // 15 days
             (d(2023, 3, 10), d(2023, 3, 25)), // 16 days
         ];
-        let results = calculate_rolling_absences(&periods);
+        let results = calculate_rolling_absences(&periods, DEFAULT_WINDOW_DAYS);
         assert_eq!(results.len(), 2);
 
         // Total unique days are from 3/1 to 3/25 = 25 days.
@@ -332,7 +934,7 @@ mod tests {
             (d(2021, 5, 1), d(2021, 5, 10)), // 10 days, old
             (d(2023, 8, 1), d(2023, 8, 20)), // 20 days, recent
         ];
-        let results = calculate_rolling_absences(&periods);
+        let results = calculate_rolling_absences(&periods, DEFAULT_WINDOW_DAYS);
         assert_eq!(results.len(), 2);
 
         // For the first period, its window only sees itself.
@@ -352,7 +954,7 @@ mod tests {
             (d(2022, 8, 15), d(2022, 8, 25)), // 11 days total
             (d(2023, 8, 20), d(2023, 8, 30)), // 11 days total
         ];
-        let results = calculate_rolling_absences(&periods);
+        let results = calculate_rolling_absences(&periods, DEFAULT_WINDOW_DAYS);
         assert_eq!(results.len(), 2);
 
         // Window for the second period ends 2023-08-30, starts 2022-08-30.
@@ -368,7 +970,7 @@ mod tests {
             (d(2022, 8, 25), d(2022, 9, 5)),  // 12 days total
             (d(2023, 8, 30), d(2023, 9, 10)), // 12 days total
         ];
-        let results = calculate_rolling_absences(&periods);
+        let results = calculate_rolling_absences(&periods, DEFAULT_WINDOW_DAYS);
 
         // Window for the second period: 2022-08-31 to 2023-08-30.
         // Overlap with first period: 2022-08-31 to 2022-09-05 (6 days).
@@ -384,4 +986,202 @@ mod tests {
         // Total should be 12 days from the second period only.
         assert_eq!(results[1].total_days_in_window, 12);
     }
+
+    #[test]
+    fn test_find_worst_window_single_absence_within_limit() {
+        let periods = vec![(d(2023, 4, 1), d(2023, 4, 10))]; // 10 days
+        let worst = find_worst_window(&periods, 365, 180).unwrap();
+        assert_eq!(worst.total_days, 10);
+        assert!(!worst.breaches_limit);
+    }
+
+    #[test]
+    fn test_find_worst_window_detects_breach_across_separate_absences() {
+        // Two absences, each under the limit alone, but both fall inside a
+        // shared 365-day window and together exceed it.
+        let periods = vec![
+            (d(2023, 1, 1), d(2023, 4, 1)),  // 91 days
+            (d(2023, 9, 1), d(2023, 12, 1)), // 92 days
+        ];
+        let worst = find_worst_window(&periods, 365, 180).unwrap();
+        assert_eq!(worst.window_end, d(2023, 12, 1));
+        assert_eq!(worst.total_days, 91 + 92);
+        assert!(worst.breaches_limit);
+    }
+
+    #[test]
+    fn test_find_worst_window_ignores_absence_outside_every_window() {
+        let periods = vec![
+            (d(2021, 5, 1), d(2021, 5, 10)), // 10 days, old
+            (d(2023, 8, 1), d(2024, 1, 10)), // 163 days, recent
+        ];
+        let worst = find_worst_window(&periods, 365, 180).unwrap();
+        assert_eq!(worst.total_days, 163);
+        assert!(!worst.breaches_limit);
+    }
+
+    #[test]
+    fn test_find_worst_window_empty_periods() {
+        assert_eq!(find_worst_window(&[], 365, 180), None);
+    }
+
+    #[test]
+    fn test_parse_args_defaults_to_text() {
+        let args: Vec<String> = vec!["prog".into(), "absences.json".into()];
+        let (file_path, format, window_mode) = parse_args(&args);
+        assert_eq!(file_path, Some("absences.json"));
+        assert_eq!(format, OutputFormat::Text);
+        assert!(matches!(
+            window_mode,
+            WindowMode::Rolling { days } if days == DEFAULT_WINDOW_DAYS
+        ));
+    }
+
+    #[test]
+    fn test_parse_args_format_flag() {
+        let args: Vec<String> = vec![
+            "prog".into(),
+            "absences.json".into(),
+            "--format".into(),
+            "json".into(),
+        ];
+        let (file_path, format, _) = parse_args(&args);
+        assert_eq!(file_path, Some("absences.json"));
+        assert_eq!(format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_parse_args_window_days_flag() {
+        let args: Vec<String> = vec![
+            "prog".into(),
+            "absences.json".into(),
+            "--window-days".into(),
+            "180".into(),
+        ];
+        let (_, _, window_mode) = parse_args(&args);
+        assert!(matches!(window_mode, WindowMode::Rolling { days: 180 }));
+    }
+
+    #[test]
+    fn test_parse_args_calendar_anchor_flag() {
+        let args: Vec<String> = vec![
+            "prog".into(),
+            "absences.json".into(),
+            "--calendar-anchor".into(),
+            "2023-01-01".into(),
+        ];
+        let (_, _, window_mode) = parse_args(&args);
+        assert!(matches!(
+            window_mode,
+            WindowMode::CalendarYears { anchor } if anchor == d(2023, 1, 1)
+        ));
+    }
+
+    #[test]
+    fn test_build_summary_reports_worst_window() {
+        let periods = vec![
+            (d(2023, 1, 1), d(2023, 4, 1)),  // 91 days
+            (d(2023, 9, 1), d(2023, 12, 1)), // 92 days
+        ];
+        let summary = build_summary(&periods, DEFAULT_WINDOW_DAYS);
+        assert_eq!(summary.results.len(), 2);
+        assert_eq!(summary.breach_threshold_days, 180);
+        let worst = summary.worst_window.unwrap();
+        assert_eq!(worst.total_days, 91 + 92);
+        assert!(worst.breaches_limit);
+    }
+
+    #[test]
+    fn test_calculate_calendar_year_blocks_splits_on_anchor() {
+        let periods = vec![
+            (d(2023, 2, 1), d(2023, 2, 10)),  // 10 days, in block 1
+            (d(2024, 2, 1), d(2024, 2, 10)),  // 10 days, in block 2
+        ];
+        let blocks = calculate_calendar_year_blocks(&periods, d(2023, 1, 1));
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].block_start, d(2023, 1, 1));
+        assert_eq!(blocks[0].block_end, d(2023, 12, 31));
+        assert_eq!(blocks[0].total_days_in_block, 10);
+        assert_eq!(blocks[1].block_start, d(2024, 1, 1));
+        assert_eq!(blocks[1].block_end, d(2024, 12, 31));
+        assert_eq!(blocks[1].total_days_in_block, 10);
+    }
+
+    #[test]
+    fn test_calculate_calendar_year_blocks_splits_absence_across_blocks() {
+        // This absence straddles the anchor boundary, so it should
+        // contribute days to both blocks it touches.
+        let periods = vec![(d(2023, 12, 25), d(2024, 1, 5))]; // 12 days total
+        let blocks = calculate_calendar_year_blocks(&periods, d(2023, 1, 1));
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].total_days_in_block, 7); // Dec 25-31
+        assert_eq!(blocks[1].total_days_in_block, 5); // Jan 1-5
+    }
+
+    #[test]
+    fn test_calculate_calendar_year_blocks_empty_periods() {
+        assert_eq!(calculate_calendar_year_blocks(&[], d(2023, 1, 1)), Vec::new());
+    }
+
+    #[test]
+    fn test_write_json_summary_is_valid_standalone_json() {
+        let periods = vec![(d(2023, 4, 1), d(2023, 4, 10))];
+        let summary = build_summary(&periods, DEFAULT_WINDOW_DAYS);
+
+        let mut buf = Vec::new();
+        write_json_summary(&summary, &mut buf).unwrap();
+
+        // The whole payload must parse as JSON on its own - no banner or other
+        // prose is allowed ahead of it, or `json.load`-style tooling breaks.
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed["results"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_write_csv_summary_is_header_then_rows_only() {
+        let periods = vec![(d(2023, 4, 1), d(2023, 4, 10))];
+        let summary = build_summary(&periods, DEFAULT_WINDOW_DAYS);
+
+        let mut buf = Vec::new();
+        write_csv_summary(&summary, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let mut lines = text.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("absence_start,absence_end,window_start,window_end,total_days_in_window")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("2023-04-01,2023-04-10,2022-04-10,2023-04-10,10")
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_write_calendar_blocks_json_is_valid_standalone_json() {
+        let periods = vec![(d(2023, 2, 1), d(2023, 2, 10))];
+        let blocks = calculate_calendar_year_blocks(&periods, d(2023, 1, 1));
+
+        let mut buf = Vec::new();
+        write_calendar_blocks_json(&blocks, &mut buf).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_write_calendar_blocks_csv_is_header_then_rows_only() {
+        let periods = vec![(d(2023, 2, 1), d(2023, 2, 10))];
+        let blocks = calculate_calendar_year_blocks(&periods, d(2023, 1, 1));
+
+        let mut buf = Vec::new();
+        write_calendar_blocks_csv(&blocks, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let mut lines = text.lines();
+
+        assert_eq!(lines.next(), Some("block_start,block_end,total_days_in_block"));
+        assert_eq!(lines.next(), Some("2023-01-01,2023-12-31,10"));
+        assert_eq!(lines.next(), None);
+    }
 }